@@ -0,0 +1,124 @@
+use std::{collections::HashMap, fs, path::Path, time::UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::Post;
+
+const CACHE_PATH: &str = "out/.build-cache.json";
+const TEMPLATES_DIR: &str = "templates";
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry {
+    mtime: u64,
+    hash: String,
+    post: Post,
+}
+
+/// On-disk cache of rendered post metadata, keyed by a post's route (its
+/// output path, derived from the source file). Invalidated wholesale
+/// whenever anything under `templates/` changes.
+#[derive(Serialize, Deserialize, Default)]
+pub struct BuildCache {
+    template_version: String,
+    #[serde(default)]
+    entries: HashMap<String, CacheEntry>,
+    /// Source mtime/hash observed this run, keyed by route, recorded while
+    /// parsing and consumed once the post's final (post-navigation) state is
+    /// known, in `mark_rendered`. Not persisted.
+    #[serde(skip)]
+    pending: HashMap<String, (u64, String)>,
+}
+
+impl BuildCache {
+    pub fn load() -> Self {
+        let current_version = template_version();
+        let cache = fs::read(CACHE_PATH)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<BuildCache>(&bytes).ok())
+            .unwrap_or_default();
+        if cache.template_version == current_version {
+            cache
+        } else {
+            BuildCache {
+                template_version: current_version,
+                ..Default::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = Path::new(CACHE_PATH).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(CACHE_PATH, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records the source mtime/hash observed for `route` this run, so
+    /// `mark_rendered` can persist it alongside the post's final state.
+    pub fn note_pending(&mut self, route: String, mtime: u64, hash: String) {
+        self.pending.insert(route, (mtime, hash));
+    }
+
+    /// Returns the cached, already-parsed post for `route` if its source
+    /// hash/mtime are unchanged, letting the caller skip re-parsing. The
+    /// returned post's series navigation may be stale and is recomputed by
+    /// the caller regardless.
+    pub fn get_parsed(&self, route: &str, mtime: u64, hash: &str) -> Option<Post> {
+        let entry = self.entries.get(route)?;
+        (entry.mtime == mtime && entry.hash == hash).then(|| entry.post.clone())
+    }
+
+    /// Whether `post` (including series navigation) is identical to what was
+    /// rendered to `out_path` last time, so the render can be skipped.
+    pub fn is_render_fresh(&self, post: &Post, out_path: &Path) -> bool {
+        match self.entries.get(&post.path) {
+            Some(entry) => entry.post == *post && out_path.exists(),
+            None => false,
+        }
+    }
+
+    /// Records `post` as the latest rendered state for its route, using the
+    /// mtime/hash observed while parsing it this run.
+    pub fn mark_rendered(&mut self, post: &Post) {
+        if let Some((mtime, hash)) = self.pending.get(&post.path).cloned() {
+            self.entries.insert(
+                post.path.clone(),
+                CacheEntry {
+                    mtime,
+                    hash,
+                    post: post.clone(),
+                },
+            );
+        }
+    }
+}
+
+fn template_version() -> String {
+    let mut paths: Vec<_> = walkdir::WalkDir::new(TEMPLATES_DIR)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    paths.sort();
+
+    let mut hasher = blake3::Hasher::new();
+    for path in paths {
+        if let Ok(bytes) = fs::read(&path) {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update(&bytes);
+        }
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+pub fn file_mtime_secs(path: &Path) -> Result<u64> {
+    let modified = fs::metadata(path)?.modified()?;
+    Ok(modified.duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use atom_syndication::{
+    Content as AtomContent, Entry as AtomEntry, Feed as AtomFeed, FixedDateTime,
+    Link as AtomLink, Person, Text as AtomText,
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+use rss::{Category, ChannelBuilder, Guid, Item, ItemBuilder};
+
+use crate::Post;
+
+/// Parses post frontmatter dates, which are almost always plain `YYYY-MM-DD`,
+/// while still accepting full RFC-3339 timestamps.
+pub fn parse_post_date(date: &str) -> Result<DateTime<FixedOffset>> {
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(date) {
+        return Ok(parsed);
+    }
+    let naive = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .with_context(|| format!("post date {:?} is neither RFC-3339 nor YYYY-MM-DD", date))?;
+    Ok(FixedOffset::east_opt(0)
+        .unwrap()
+        .from_local_datetime(&naive.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap())
+}
+
+pub fn build_rss(title: &str, description: &str, posts: &[Post]) -> Result<String> {
+    let items = posts
+        .iter()
+        .map(|post| -> Result<Item> {
+            let pub_date = parse_post_date(&post.matter.date)?.to_rfc2822();
+            let categories = post
+                .matter
+                .tags
+                .iter()
+                .map(|tag| Category {
+                    name: tag.clone(),
+                    domain: None,
+                })
+                .collect::<Vec<_>>();
+            Ok(ItemBuilder::default()
+                .title(Some(post.matter.title.clone()))
+                .description(Some(post.matter.summary.clone()))
+                .link(Some(post.full_url.clone()))
+                .guid(Some(Guid {
+                    value: post.full_url.clone(),
+                    permalink: true,
+                }))
+                .pub_date(Some(pub_date))
+                .content(Some(post.content.clone()))
+                .categories(categories)
+                .build())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let channel = ChannelBuilder::default()
+        .title(title)
+        .link(crate::ORIGIN)
+        .description(description)
+        .namespace(("content".into(), "http://purl.org/rss/1.0/modules/content/".into()))
+        .items(items)
+        .build();
+
+    Ok(channel.to_string())
+}
+
+pub fn build_atom(title: &str, description: &str, posts: &[Post]) -> Result<String> {
+    let entries = posts
+        .iter()
+        .map(|post| -> Result<AtomEntry> {
+            let updated: FixedDateTime = parse_post_date(&post.matter.date)?;
+            let mut entry = AtomEntry::default();
+            entry.set_title(post.matter.title.clone());
+            entry.set_id(post.full_url.clone());
+            entry.set_updated(updated);
+            entry.set_summary(Some(AtomText::plain(post.matter.summary.clone())));
+            entry.set_content(Some(AtomContent {
+                value: Some(post.content.clone()),
+                content_type: Some(String::from("html")),
+                ..Default::default()
+            }));
+            entry.set_links(vec![AtomLink {
+                href: post.full_url.clone(),
+                rel: String::from("alternate"),
+                ..Default::default()
+            }]);
+            entry.set_categories(
+                post.matter
+                    .tags
+                    .iter()
+                    .map(|tag| atom_syndication::Category {
+                        term: tag.clone(),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            Ok(entry)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let updated = entries
+        .iter()
+        .map(|e| *e.updated())
+        .max()
+        .unwrap_or_else(|| DateTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap());
+
+    let mut feed = AtomFeed::default();
+    feed.set_title(title);
+    feed.set_subtitle(Some(AtomText::plain(description)));
+    feed.set_id(crate::ORIGIN);
+    feed.set_updated(updated);
+    feed.set_links(vec![AtomLink {
+        href: crate::ORIGIN.to_string(),
+        rel: String::from("alternate"),
+        ..Default::default()
+    }]);
+    feed.set_authors(vec![Person {
+        name: String::from("lndev"),
+        ..Default::default()
+    }]);
+    feed.set_entries(entries);
+
+    Ok(feed.to_string())
+}
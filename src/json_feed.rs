@@ -0,0 +1,40 @@
+use anyhow::Result;
+use jsonfeed::{Feed, Item, Version};
+
+use crate::{feed, Post, ORIGIN};
+
+fn item_from_post(post: &Post) -> Result<Item> {
+    let date_modified = post
+        .matter
+        .modified
+        .as_deref()
+        .map(|date| feed::parse_post_date(date).map(|d| d.to_rfc3339()))
+        .transpose()?;
+    Ok(Item {
+        id: post.full_url.clone(),
+        url: Some(post.full_url.clone()),
+        title: Some(post.matter.title.clone()),
+        content_html: Some(post.content.clone()),
+        summary: Some(post.matter.summary.clone()),
+        date_published: Some(feed::parse_post_date(&post.matter.date)?.to_rfc3339()),
+        date_modified,
+        image: Some(post.full_image_url.clone()),
+        tags: Some(post.matter.tags.clone()),
+        ..Default::default()
+    })
+}
+
+pub fn build(title: &str, description: &str, feed_url: &str, posts: &[Post]) -> Result<Feed> {
+    Ok(Feed {
+        version: Version::Version1_1,
+        title: String::from(title),
+        home_page_url: Some(String::from(ORIGIN)),
+        feed_url: Some(String::from(feed_url)),
+        description: Some(String::from(description)),
+        items: posts
+            .iter()
+            .map(item_from_post)
+            .collect::<Result<Vec<_>>>()?,
+        ..Default::default()
+    })
+}
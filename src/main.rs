@@ -4,18 +4,25 @@ use estimated_read_time::Options;
 use gray_matter::engine::YAML;
 use gray_matter::Matter;
 use minify_html::{minify, Cfg};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 
 use walkdir::WalkDir;
 
+mod cache;
+mod feed;
+mod json_feed;
+mod org;
+mod series;
+mod tags;
+
 const ORIGIN: &str = "https://lndev.nl";
 const OUT_DIR: &str = "out";
 const PUBLIC_DIR: &str = "public";
 const POSTS_DIR: &str = "posts";
 const DRAFTS_DIR: &str = "drafts";
 
-#[derive(Template, Clone)]
+#[derive(Template, Clone, Serialize, Deserialize, PartialEq)]
 #[template(path = "post.html")]
 struct Post {
     matter: PostMatter,
@@ -31,14 +38,19 @@ struct Post {
     facebook: Social,
     whatsapp: Social,
     telegram: Social,
+    series_name: Option<String>,
+    series_position: Option<usize>,
+    series_len: Option<usize>,
+    prev_in_series: Option<(String, String)>,
+    next_in_series: Option<(String, String)>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
 struct Social {
     url: String,
     label: String,
 }
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 struct PostMatter {
     title: String,
     summary: String,
@@ -46,9 +58,10 @@ struct PostMatter {
     date: String,
     modified: Option<String>,
     tags: Vec<String>,
+    series: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 struct CoverMatter {
     image: String,
 }
@@ -63,19 +76,73 @@ struct Blog {
 
 fn main() -> Result<()> {
     copy_dir_all(PUBLIC_DIR, OUT_DIR)?;
-    let posts = collect_posts(POSTS_DIR)?;
-    let drafts = collect_posts(DRAFTS_DIR)?;
-    let all_posts = posts.clone().into_iter().chain(drafts.clone().into_iter());
-    for post in all_posts {
-        let html = post.render()?;
-        let path = Path::new(OUT_DIR).join(&post.path.trim_start_matches("/"));
-        write_file(&path.join("index.html"), html)?;
+    let mut build_cache = cache::BuildCache::load();
+    let posts = collect_posts(POSTS_DIR, &mut build_cache)?;
+    let drafts = collect_posts(DRAFTS_DIR, &mut build_cache)?;
+    for post in posts.iter().chain(drafts.iter()) {
+        render_post_page(post, &mut build_cache)?;
+    }
+    build_cache.save()?;
+
+    let blog_description = "Where insights are shared on development on the lightning network.";
+    let rss = feed::build_rss("lndev - blog", blog_description, &posts)?;
+    write_file(&Path::new(OUT_DIR).join("feed.xml"), rss)?;
+    let atom = feed::build_atom("lndev - blog", blog_description, &posts)?;
+    write_file(&Path::new(OUT_DIR).join("atom.xml"), atom)?;
+    let json_feed = json_feed::build(
+        "lndev - blog",
+        blog_description,
+        &format!("{}/feed.json", ORIGIN),
+        &posts,
+    )?;
+    write_file(
+        &Path::new(OUT_DIR).join("feed.json"),
+        serde_json::to_string_pretty(&json_feed)?,
+    )?;
+
+    let by_tag = tags::group_by_tag(&posts);
+    let tagsdir = Path::new(OUT_DIR).join("tags");
+    let mut tag_counts = Vec::with_capacity(by_tag.len());
+    for (tag, tag_posts) in &by_tag {
+        let slug = tags::slugify(tag);
+        let tag_page = tags::TagPage {
+            tag: tag.clone(),
+            page_title: format!("lndev - {}", tag),
+            posts: tag_posts.clone(),
+        };
+        write_file(
+            &tagsdir.join(&slug).join("index.html"),
+            tag_page.render()?,
+        )?;
+        tag_counts.push(tags::TagCount {
+            tag: tag.clone(),
+            slug,
+            count: tag_posts.len(),
+        });
+    }
+    let tag_index = tags::TagIndex {
+        page_title: String::from("lndev - tags"),
+        tags: tag_counts,
+    };
+    write_file(&tagsdir.join("index.html"), tag_index.render()?)?;
+
+    let by_series = series::group_by_series(&posts);
+    let seriesdir = Path::new(OUT_DIR).join("series");
+    for (name, series_posts) in &by_series {
+        let slug = tags::slugify(name);
+        let series_page = series::SeriesPage {
+            series: name.clone(),
+            page_title: format!("lndev - {}", name),
+            posts: series_posts.clone(),
+        };
+        write_file(
+            &seriesdir.join(&slug).join("index.html"),
+            series_page.render()?,
+        )?;
     }
 
     let blog = Blog {
-        description: String::from(
-            "Where insights are shared on development on the lightning network.",
-        ),
+        description: String::from(blog_description),
         page_title: String::from("lndev - blog"),
         posts,
     };
@@ -146,20 +213,50 @@ fn minify_html(contents: &[u8]) -> Vec<u8> {
     minify(contents, &cfg)
 }
 
-fn collect_posts(dir: impl AsRef<Path>) -> Result<Vec<Post>> {
+fn collect_posts(dir: impl AsRef<Path>, build_cache: &mut cache::BuildCache) -> Result<Vec<Post>> {
     let mut posts = WalkDir::new(dir)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
         .filter_map(|e| e.path().to_str().map(|e| String::from(e)))
-        .filter(|e| e.ends_with(".md"))
-        .map(|p| get_post(Path::new(&p)))
+        .filter(|e| e.ends_with(".md") || e.ends_with(".org"))
+        .map(|p| {
+            let source_path = Path::new(&p);
+            let route = route_for(source_path);
+            let bytes = fs::read(source_path)?;
+            let hash = cache::hash_bytes(&bytes);
+            let mtime = cache::file_mtime_secs(source_path)?;
+            build_cache.note_pending(route.clone(), mtime, hash.clone());
+            if let Some(post) = build_cache.get_parsed(&route, mtime, &hash) {
+                return Ok(post);
+            }
+            get_post(source_path)
+        })
         .collect::<Result<Vec<Post>>>()?;
+    // Re-run navigation and sorting even on a cache hit: a neighbor's
+    // `prev_in_series`/`next_in_series` may have changed even though this
+    // post's own content didn't.
+    series::assign_series_navigation(&mut posts);
     posts.sort_by(|a, b| b.matter.date.cmp(&a.matter.date));
     Ok(posts)
 }
 
-fn get_post(relative_path: &Path) -> Result<Post> {
+/// Renders and writes a post's page, skipping the work when an identical
+/// `Post` (including series navigation) was already rendered to disk.
+fn render_post_page(post: &Post, build_cache: &mut cache::BuildCache) -> Result<()> {
+    let out_path = Path::new(OUT_DIR)
+        .join(post.path.trim_start_matches("/"))
+        .join("index.html");
+    if !build_cache.is_render_fresh(post, &out_path) {
+        write_file(&out_path, post.render()?)?;
+    }
+    build_cache.mark_rendered(post);
+    Ok(())
+}
+
+/// The site-relative route a post renders to, derived purely from its source
+/// path so it can be computed before the file is parsed.
+fn route_for(relative_path: &Path) -> String {
     let slug = relative_path
         .file_stem()
         .unwrap()
@@ -177,6 +274,11 @@ fn get_post(relative_path: &Path) -> Result<Post> {
     if !path.starts_with("/") {
         path.insert_str(0, "/");
     }
+    path
+}
+
+fn get_post(relative_path: &Path) -> Result<Post> {
+    let path = route_for(relative_path);
     let origin = String::from(ORIGIN);
     let full_url = origin.clone() + &path;
     let file_content = fs::read_to_string(relative_path)?;
@@ -184,7 +286,10 @@ fn get_post(relative_path: &Path) -> Result<Post> {
         .parse_with_struct::<PostMatter>(&file_content)
         .unwrap();
 
-    let content = markdown::to_html(&parsed.content);
+    let content = match relative_path.extension().and_then(|e| e.to_str()) {
+        Some("org") => org::render_body(&parsed.content)?,
+        _ => markdown::to_html(&parsed.content),
+    };
     let read_time_seconds =
         estimated_read_time::text(&parsed.content, &Options::new().build().unwrap()).seconds();
     let read_time = if read_time_seconds < 60 {
@@ -233,5 +338,10 @@ fn get_post(relative_path: &Path) -> Result<Post> {
             url: format!("https://telegram.me/share/url?text={}&url={}", encoded_title, encoded_url),
             label: lbl("Telegram"),
         },
+        series_name: None,
+        series_position: None,
+        series_len: None,
+        prev_in_series: None,
+        next_in_series: None,
     })
 }
@@ -0,0 +1,9 @@
+use anyhow::Result;
+use orgize::Org;
+
+/// Renders the body of an Org-mode document (frontmatter already stripped) to HTML.
+pub fn render_body(content: &str) -> Result<String> {
+    let mut html = Vec::new();
+    Org::parse(content).write_html(&mut html)?;
+    Ok(String::from_utf8(html)?)
+}
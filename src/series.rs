@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use askama::Template;
+
+use crate::Post;
+
+#[derive(Template)]
+#[template(path = "series.html")]
+pub struct SeriesPage {
+    pub series: String,
+    pub page_title: String,
+    pub posts: Vec<Post>,
+}
+
+/// Fills in `series_name`, `prev_in_series` and `next_in_series` on every post
+/// that shares a `matter.series` value with at least one other post, ordering
+/// each series by `matter.date`.
+pub fn assign_series_navigation(posts: &mut [Post]) {
+    let mut by_series: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, post) in posts.iter_mut().enumerate() {
+        // Clear any navigation carried over from a cached copy of the post.
+        post.series_name = None;
+        post.series_position = None;
+        post.series_len = None;
+        post.prev_in_series = None;
+        post.next_in_series = None;
+        if let Some(series) = &post.matter.series {
+            by_series.entry(series.clone()).or_default().push(i);
+        }
+    }
+
+    for indices in by_series.values().filter(|indices| indices.len() > 1) {
+        let mut ordered = indices.clone();
+        ordered.sort_by_key(|&i| posts[i].matter.date.clone());
+        for (pos, &i) in ordered.iter().enumerate() {
+            let prev = pos
+                .checked_sub(1)
+                .map(|p| ordered[p])
+                .map(|j| (posts[j].matter.title.clone(), posts[j].path.clone()));
+            let next = ordered
+                .get(pos + 1)
+                .map(|&j| (posts[j].matter.title.clone(), posts[j].path.clone()));
+            posts[i].series_name = posts[i].matter.series.clone();
+            posts[i].series_position = Some(pos + 1);
+            posts[i].series_len = Some(ordered.len());
+            posts[i].prev_in_series = prev;
+            posts[i].next_in_series = next;
+        }
+    }
+}
+
+/// Groups posts by series name, each group ordered oldest-first (reading order).
+pub fn group_by_series(posts: &[Post]) -> BTreeMap<String, Vec<Post>> {
+    let mut by_series: BTreeMap<String, Vec<Post>> = BTreeMap::new();
+    for post in posts {
+        if let Some(series) = &post.matter.series {
+            by_series.entry(series.clone()).or_default().push(post.clone());
+        }
+    }
+    by_series.retain(|_, posts| posts.len() > 1);
+    for posts in by_series.values_mut() {
+        posts.sort_by(|a, b| a.matter.date.cmp(&b.matter.date));
+    }
+    by_series
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use askama::Template;
+
+use crate::Post;
+
+#[derive(Template)]
+#[template(path = "tag.html")]
+pub struct TagPage {
+    pub tag: String,
+    pub page_title: String,
+    pub posts: Vec<Post>,
+}
+
+pub struct TagCount {
+    pub tag: String,
+    pub slug: String,
+    pub count: usize,
+}
+
+#[derive(Template)]
+#[template(path = "tags.html")]
+pub struct TagIndex {
+    pub page_title: String,
+    pub tags: Vec<TagCount>,
+}
+
+pub fn group_by_tag(posts: &[Post]) -> BTreeMap<String, Vec<Post>> {
+    let mut by_tag: BTreeMap<String, Vec<Post>> = BTreeMap::new();
+    for post in posts {
+        for tag in &post.matter.tags {
+            by_tag.entry(tag.clone()).or_default().push(post.clone());
+        }
+    }
+    for posts in by_tag.values_mut() {
+        posts.sort_by(|a, b| b.matter.date.cmp(&a.matter.date));
+    }
+    by_tag
+}
+
+pub fn slugify(tag: &str) -> String {
+    let mut slug = String::with_capacity(tag.len());
+    let mut last_was_dash = false;
+    for ch in tag.to_lowercase().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch);
+            last_was_dash = false;
+        } else if !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
+}